@@ -33,14 +33,14 @@ fn main() -> ! {
 
     let motor_right: MotorFastDecay = Motor::new(
         &ledc,
-        &motor_timer_conf.timer,
+        &motor_timer_conf,
         MotorLink::new(channel::Number::Channel0, peripherals.GPIO1),
         MotorLink::new(channel::Number::Channel1, peripherals.GPIO2),
     )
     .unwrap();
     let motor_left: MotorFastDecay = Motor::new(
         &ledc,
-        &motor_timer_conf.timer,
+        &motor_timer_conf,
         MotorLink::new(channel::Number::Channel2, peripherals.GPIO3),
         MotorLink::new(channel::Number::Channel3, peripherals.GPIO4),
     )
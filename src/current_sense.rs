@@ -0,0 +1,48 @@
+use esp_hal::{
+    analog::adc::{Adc, AdcCalScheme, AdcChannel, AdcPin, RegisterAccess},
+    Blocking,
+};
+
+use crate::drv8833::Error;
+
+/// Wraps an ADC channel bound to the DRV8833 current-sense resistor node, mirroring the
+/// channel/pin pairing already used for LEDC channels elsewhere in this crate.
+///
+/// `CS` is the ADC calibration scheme, matching [`AdcPin`]'s own default of no calibration.
+pub struct CurrentSense<'a, ADCI, PIN, CS = ()>
+where
+    ADCI: RegisterAccess,
+    PIN: AdcChannel,
+    CS: AdcCalScheme<ADCI>,
+{
+    adc: Adc<'a, ADCI, Blocking>,
+    pin: AdcPin<PIN, ADCI, CS>,
+}
+
+impl<'a, ADCI, PIN, CS> CurrentSense<'a, ADCI, PIN, CS>
+where
+    ADCI: RegisterAccess,
+    PIN: AdcChannel,
+    CS: AdcCalScheme<ADCI>,
+{
+    pub fn new(adc: Adc<'a, ADCI, Blocking>, pin: AdcPin<PIN, ADCI, CS>) -> Self {
+        Self { adc, pin }
+    }
+
+    /// Reads one ADC sample off the sense line, post-processed by the pin's calibration scheme
+    /// `CS` (no calibration with the default `()`).
+    pub fn read_raw(&mut self) -> Result<u16, Error> {
+        nb::block!(self.adc.read_oneshot(&mut self.pin)).map_err(|_| Error::AdcError)
+    }
+
+    /// Reads the sense line and converts it to milliamps, assuming the sampled voltage is the
+    /// drop across `r_sense_milliohm` (the sense resistor between the DRV8833 and ground).
+    pub fn read_milliamps(&mut self, r_sense_milliohm: u32) -> Result<u32, Error> {
+        let raw = self.read_raw()?;
+
+        // 12-bit ADC sample over the esp32's ~3300mV attenuated full scale.
+        let millivolts = (raw as u32 * 3300) / 4095;
+
+        Ok((millivolts * 1000) / r_sense_milliohm)
+    }
+}
@@ -1,18 +1,31 @@
+use core::cell::Cell;
+
+use crate::current_sense::CurrentSense;
 use esp_hal::{
+    analog::adc::{AdcCalScheme, AdcChannel, RegisterAccess},
     delay::Delay,
     gpio::{interconnect::PeripheralOutput, Level, Output, OutputConfig, OutputPin},
     ledc::{
-        channel::{self, Channel, ChannelIFace},
-        timer::{self, config::Duty, Timer, TimerIFace},
+        channel::{self, Channel, ChannelHW, ChannelIFace},
+        timer::{self, config::Duty, Timer, TimerIFace, TimerSpeed},
         Ledc, LowSpeed,
     },
-    time::Rate,
+    time::{Duration, Instant, Rate},
 };
+#[cfg(esp32)]
+use esp_hal::ledc::HighSpeed;
+use libm::sqrtf;
 
 #[derive(Debug)]
 pub enum Error {
     ChannelError(esp_hal::ledc::channel::Error),
     TimerError(esp_hal::ledc::timer::Error),
+    /// The ADC sample taken by a [`crate::current_sense::CurrentSense`] failed.
+    AdcError,
+    /// [`MotorInterface::forward_guarded`] tripped: current exceeded the configured threshold.
+    OverCurrent,
+    /// A sensed [`Stepper`] move was aborted because the rotor failed to advance under load.
+    Stall,
 }
 
 impl From<esp_hal::ledc::channel::Error> for Error {
@@ -27,17 +40,24 @@ impl From<esp_hal::ledc::timer::Error> for Error {
     }
 }
 
-pub struct MotorTimer<'a> {
-    pub timer: Timer<'a, LowSpeed>,
+/// A LEDC timer driving one or more motors, generic over the LEDC speed domain `S` (the
+/// low-speed domain, [`LowSpeed`], is the default: it is better suited to typical motor
+/// frequencies, more power efficient, and keeps working under sleep modes; on the original
+/// ESP32, the high-speed domain `HighSpeed` trades that for more duty-resolution bits and
+/// glitch-free updates at a given carrier frequency — see `MotorTimer::new_high_speed`, only
+/// available on that chip since later ESP32 variants dropped the high-speed LEDC domain).
+pub struct MotorTimer<'a, S: TimerSpeed = LowSpeed> {
+    pub timer: Timer<'a, S>,
+    duty: Duty,
 }
 
-impl<'a> MotorTimer<'a> {
+impl<'a> MotorTimer<'a, LowSpeed> {
     pub fn new(
         ledc: &'a Ledc<'a>,
         timer: timer::Number,
         duty: Duty,
         frequency: Rate,
-    ) -> Result<MotorTimer<'a>, Error> {
+    ) -> Result<MotorTimer<'a, LowSpeed>, Error> {
         let mut lstimer = ledc.timer::<LowSpeed>(timer);
         lstimer.configure(timer::config::Config {
             duty,
@@ -45,7 +65,71 @@ impl<'a> MotorTimer<'a> {
             frequency,
         })?;
 
-        Ok(Self { timer: lstimer })
+        Ok(Self {
+            timer: lstimer,
+            duty,
+        })
+    }
+
+    /// Reconfigures this timer to run at `rate`.
+    ///
+    /// `MotorTimer` doesn't hold references to the channels linked to it, so this can't rewrite
+    /// any channel's duty register directly. It doesn't need to: LEDC stores a channel's duty as
+    /// a fraction of the timer's resolution (`Duty`), not as an absolute on-time, and this keeps
+    /// the resolution untouched, so every linked channel's speed ratio is preserved by the
+    /// hardware across the frequency switch with no software-side recompute required.
+    pub fn set_frequency(&mut self, rate: Rate) -> Result<(), Error> {
+        self.timer.configure(timer::config::Config {
+            duty: self.duty,
+            clock_source: timer::LSClockSource::APBClk,
+            frequency: rate,
+        })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(esp32)]
+impl<'a> MotorTimer<'a, HighSpeed> {
+    /// Configures this timer in the LEDC high-speed domain (`HSGlobalClkSource`) instead of the
+    /// default low-speed one, for more duty-resolution bits at a given carrier frequency and
+    /// independent glitch-free duty updates.
+    pub fn new_high_speed(
+        ledc: &'a Ledc<'a>,
+        timer: timer::Number,
+        duty: Duty,
+        frequency: Rate,
+    ) -> Result<MotorTimer<'a, HighSpeed>, Error> {
+        let mut hstimer = ledc.timer::<HighSpeed>(timer);
+        hstimer.configure(timer::config::Config {
+            duty,
+            clock_source: timer::HSClockSource::APBClk,
+            frequency,
+        })?;
+
+        Ok(Self {
+            timer: hstimer,
+            duty,
+        })
+    }
+
+    /// Same as [`MotorTimer::set_frequency`], for a timer in the high-speed domain.
+    pub fn set_frequency(&mut self, rate: Rate) -> Result<(), Error> {
+        self.timer.configure(timer::config::Config {
+            duty: self.duty,
+            clock_source: timer::HSClockSource::APBClk,
+            frequency: rate,
+        })?;
+
+        Ok(())
+    }
+}
+
+impl<'a, S: TimerSpeed> MotorTimer<'a, S> {
+    /// The duty resolution this timer was configured with, e.g. for scaling a [`u16`] duty
+    /// via [`MotorInterface::forward_u16`]/[`MotorInterface::backward_u16`].
+    pub fn duty_resolution(&self) -> Duty {
+        self.duty
     }
 }
 
@@ -78,56 +162,153 @@ impl Motor {
     /// ```rust
     /// let motor: MotorFastDecay = Motor::new(&timer, peripherals.GPIO1, peripherals.GPIO2)?;
     /// ```
-    pub fn new<'a, M, A, B>(
+    pub fn new<'a, S, M, A, B>(
         ledc: &'a Ledc<'a>,
-        timer: &'a Timer<'a, LowSpeed>,
+        motor_timer: &'a MotorTimer<'a, S>,
         motor_link_a: MotorLink<A>,
         motor_link_b: MotorLink<B>,
     ) -> Result<M, Error>
     where
-        M: MotorInterface<'a>,
+        S: TimerSpeed,
+        M: MotorInterface<'a, S>,
         A: for<'any> PeripheralOutput<'any>,
         B: for<'any> PeripheralOutput<'any>,
     {
         let mut channel_a = ledc.channel(motor_link_a.channel_num, motor_link_a.gpio);
         channel_a.configure(channel::config::Config {
-            timer: timer,
+            timer: &motor_timer.timer,
             duty_pct: 0,
             pin_config: channel::config::PinConfig::PushPull,
         })?;
 
         let mut channel_b = ledc.channel(motor_link_b.channel_num, motor_link_b.gpio);
         channel_b.configure(channel::config::Config {
-            timer: timer,
+            timer: &motor_timer.timer,
             duty_pct: 0,
             pin_config: channel::config::PinConfig::PushPull,
         })?;
 
-        Ok(M::new(channel_a, channel_b))
+        Ok(M::new(channel_a, channel_b, motor_timer.duty))
     }
 }
 
-pub trait MotorInterface<'a> {
-    fn new(a: Channel<'a, LowSpeed>, b: Channel<'a, LowSpeed>) -> Self;
+/// Tracks which channel is currently driving the motor, so that [`MotorInterface::fade_to`]
+/// knows which one to ramp and which one to leave at rest.
+#[derive(Clone, Copy)]
+enum Direction {
+    Forward,
+    Backward,
+}
+
+/// Rescales a 0..=65535 duty to the raw duty-register count for the given timer resolution.
+fn scale_u16_to_resolution(duty: u16, resolution: Duty) -> u32 {
+    let max = (1u32 << resolution as u8) - 1;
+
+    (duty as u32 * max) / u16::MAX as u32
+}
+
+/// Downscales a 0..=65535 duty to the 0-100 percentage tracked for [`MotorInterface::fade_to`].
+fn duty_u16_to_pct(duty: u16) -> u8 {
+    ((duty as u32 * 100) / u16::MAX as u32) as u8
+}
+
+/// Clamps a fade `duration` to the `u16` milliseconds [`start_duty_fade`](ChannelIFace::start_duty_fade)
+/// takes, instead of truncating it, so a fade longer than ~65.5s saturates at the hardware's
+/// longest fade instead of wrapping around to a near-instant one.
+fn fade_duration_ms(duration: Duration) -> u16 {
+    duration.as_millis().min(u16::MAX as u64) as u16
+}
+
+pub trait MotorInterface<'a, S: TimerSpeed = LowSpeed> {
+    fn new(a: Channel<'a, S>, b: Channel<'a, S>, duty_resolution: Duty) -> Self;
     fn forward(&self, duty: u8) -> Result<(), Error>;
     fn backward(&self, duty: u8) -> Result<(), Error>;
     fn brake(&self) -> Result<(), Error>;
+
+    /// Same as [`MotorInterface::forward`], but takes a high-resolution 0..=65535 duty that is
+    /// rescaled to the timer's configured [`Duty`] resolution instead of a 0-100 percentage.
+    fn forward_u16(&self, duty: u16) -> Result<(), Error>;
+
+    /// Same as [`MotorInterface::backward`], but takes a high-resolution 0..=65535 duty that is
+    /// rescaled to the timer's configured [`Duty`] resolution instead of a 0-100 percentage.
+    fn backward_u16(&self, duty: u16) -> Result<(), Error>;
+
+    /// Ramps the active channel from its current duty to `duty` over `duration`, using the
+    /// LEDC hardware duty fade, instead of jumping to the target duty in one step.
+    ///
+    /// The direction (forward/backward) is whichever was last set via [`MotorInterface::forward`]
+    /// or [`MotorInterface::backward`]; the opposite channel is left at rest.
+    fn fade_to(&self, duty: u8, duration: Duration) -> Result<(), Error>;
+
+    /// Fades the motor to a stop over `duration`, instead of braking instantly.
+    fn fade_brake(&self, duration: Duration) -> Result<(), Error> {
+        self.fade_to(0, duration)
+    }
+
+    /// Same as [`MotorInterface::forward`], but polls `sense` every `poll_interval` for
+    /// `window` afterwards and automatically [`MotorInterface::brake`]s, returning
+    /// [`Error::OverCurrent`], if the current drawn across `r_sense_milliohm` ever exceeds
+    /// `threshold_ma` during that window.
+    fn forward_guarded<ADCI, PIN, CS>(
+        &self,
+        duty: u8,
+        sense: &mut CurrentSense<'_, ADCI, PIN, CS>,
+        r_sense_milliohm: u32,
+        threshold_ma: u32,
+        delay: &Delay,
+        poll_interval: Duration,
+        window: Duration,
+    ) -> Result<(), Error>
+    where
+        ADCI: RegisterAccess,
+        PIN: AdcChannel,
+        CS: AdcCalScheme<ADCI>,
+    {
+        self.forward(duty)?;
+
+        let poll_interval_us = poll_interval.as_micros().max(1);
+        let polls = (window.as_micros() / poll_interval_us).max(1);
+
+        for _ in 0..polls {
+            delay.delay_micros(poll_interval_us as u32);
+
+            if sense.read_milliamps(r_sense_milliohm)? > threshold_ma {
+                self.brake()?;
+
+                return Err(Error::OverCurrent);
+            }
+        }
+
+        Ok(())
+    }
 }
 
-pub struct MotorFastDecay<'a> {
-    a: Channel<'a, LowSpeed>,
-    b: Channel<'a, LowSpeed>,
+pub struct MotorFastDecay<'a, S: TimerSpeed = LowSpeed> {
+    a: Channel<'a, S>,
+    b: Channel<'a, S>,
+    direction: Cell<Direction>,
+    duty: Cell<u8>,
+    duty_resolution: Duty,
 }
 
-impl<'a> MotorInterface<'a> for MotorFastDecay<'a> {
-    fn new(a: Channel<'a, LowSpeed>, b: Channel<'a, LowSpeed>) -> Self {
-        Self { a, b }
+impl<'a, S: TimerSpeed> MotorInterface<'a, S> for MotorFastDecay<'a, S> {
+    fn new(a: Channel<'a, S>, b: Channel<'a, S>, duty_resolution: Duty) -> Self {
+        Self {
+            a,
+            b,
+            direction: Cell::new(Direction::Forward),
+            duty: Cell::new(0),
+            duty_resolution,
+        }
     }
 
     fn forward(&self, duty: u8) -> Result<(), Error> {
         self.a.set_duty(duty)?;
         self.b.set_duty(0)?;
 
+        self.direction.set(Direction::Forward);
+        self.duty.set(duty);
+
         Ok(())
     }
 
@@ -135,6 +316,29 @@ impl<'a> MotorInterface<'a> for MotorFastDecay<'a> {
         self.a.set_duty(0)?;
         self.b.set_duty(duty)?;
 
+        self.direction.set(Direction::Backward);
+        self.duty.set(duty);
+
+        Ok(())
+    }
+
+    fn forward_u16(&self, duty: u16) -> Result<(), Error> {
+        self.a.set_duty_hw(scale_u16_to_resolution(duty, self.duty_resolution));
+        self.b.set_duty(0)?;
+
+        self.direction.set(Direction::Forward);
+        self.duty.set(duty_u16_to_pct(duty));
+
+        Ok(())
+    }
+
+    fn backward_u16(&self, duty: u16) -> Result<(), Error> {
+        self.a.set_duty(0)?;
+        self.b.set_duty_hw(scale_u16_to_resolution(duty, self.duty_resolution));
+
+        self.direction.set(Direction::Backward);
+        self.duty.set(duty_u16_to_pct(duty));
+
         Ok(())
     }
 
@@ -142,24 +346,51 @@ impl<'a> MotorInterface<'a> for MotorFastDecay<'a> {
         self.a.set_duty(0)?;
         self.b.set_duty(0)?;
 
+        self.duty.set(0);
+
+        Ok(())
+    }
+
+    fn fade_to(&self, duty: u8, duration: Duration) -> Result<(), Error> {
+        let (active, rest) = match self.direction.get() {
+            Direction::Forward => (&self.a, &self.b),
+            Direction::Backward => (&self.b, &self.a),
+        };
+
+        rest.set_duty(0)?;
+        active.start_duty_fade(self.duty.get(), duty, fade_duration_ms(duration))?;
+        self.duty.set(duty);
+
         Ok(())
     }
 }
 
-pub struct MotorSlowDecay<'a> {
-    a: Channel<'a, LowSpeed>,
-    b: Channel<'a, LowSpeed>,
+pub struct MotorSlowDecay<'a, S: TimerSpeed = LowSpeed> {
+    a: Channel<'a, S>,
+    b: Channel<'a, S>,
+    direction: Cell<Direction>,
+    duty: Cell<u8>,
+    duty_resolution: Duty,
 }
 
-impl<'a> MotorInterface<'a> for MotorSlowDecay<'a> {
-    fn new(a: Channel<'a, LowSpeed>, b: Channel<'a, LowSpeed>) -> Self {
-        Self { a, b }
+impl<'a, S: TimerSpeed> MotorInterface<'a, S> for MotorSlowDecay<'a, S> {
+    fn new(a: Channel<'a, S>, b: Channel<'a, S>, duty_resolution: Duty) -> Self {
+        Self {
+            a,
+            b,
+            direction: Cell::new(Direction::Forward),
+            duty: Cell::new(0),
+            duty_resolution,
+        }
     }
 
     fn forward(&self, duty: u8) -> Result<(), Error> {
         self.a.set_duty(100)?;
         self.b.set_duty(100 - duty)?;
 
+        self.direction.set(Direction::Forward);
+        self.duty.set(duty);
+
         Ok(())
     }
 
@@ -167,6 +398,35 @@ impl<'a> MotorInterface<'a> for MotorSlowDecay<'a> {
         self.a.set_duty(100 - duty)?;
         self.b.set_duty(100)?;
 
+        self.direction.set(Direction::Backward);
+        self.duty.set(duty);
+
+        Ok(())
+    }
+
+    fn forward_u16(&self, duty: u16) -> Result<(), Error> {
+        let max = (1u32 << self.duty_resolution as u8) - 1;
+
+        self.a.set_duty_hw(max);
+        self.b
+            .set_duty_hw(max - scale_u16_to_resolution(duty, self.duty_resolution));
+
+        self.direction.set(Direction::Forward);
+        self.duty.set(duty_u16_to_pct(duty));
+
+        Ok(())
+    }
+
+    fn backward_u16(&self, duty: u16) -> Result<(), Error> {
+        let max = (1u32 << self.duty_resolution as u8) - 1;
+
+        self.a
+            .set_duty_hw(max - scale_u16_to_resolution(duty, self.duty_resolution));
+        self.b.set_duty_hw(max);
+
+        self.direction.set(Direction::Backward);
+        self.duty.set(duty_u16_to_pct(duty));
+
         Ok(())
     }
 
@@ -174,8 +434,185 @@ impl<'a> MotorInterface<'a> for MotorSlowDecay<'a> {
         self.a.set_duty(100)?;
         self.b.set_duty(100)?;
 
+        self.duty.set(0);
+
         Ok(())
     }
+
+    fn fade_to(&self, duty: u8, duration: Duration) -> Result<(), Error> {
+        // The variable channel runs inverted (100 - duty); the other stays pinned at 100.
+        let (active, rest) = match self.direction.get() {
+            Direction::Forward => (&self.b, &self.a),
+            Direction::Backward => (&self.a, &self.b),
+        };
+
+        rest.set_duty(100)?;
+        active.start_duty_fade(100 - self.duty.get(), 100 - duty, fade_duration_ms(duration))?;
+        self.duty.set(duty);
+
+        Ok(())
+    }
+}
+
+/// The raw duty-register ceiling for a timer's configured [`Duty`] resolution, clamped to
+/// `u16` for `embedded-hal`'s [`SetDutyCycle::max_duty_cycle`](embedded_hal::pwm::SetDutyCycle::max_duty_cycle).
+#[cfg(feature = "embedded-hal")]
+fn duty_resolution_max_u16(resolution: Duty) -> u16 {
+    ((1u32 << resolution as u8) - 1).min(u16::MAX as u32) as u16
+}
+
+/// Exposes a single LEDC channel owned by a [`MotorPwm`] or [`MotorPwmSlowDecay`] as an
+/// `embedded-hal` [`SetDutyCycle`] output, so generic motion libraries can drive a DRV8833
+/// without depending on `esp-hal`.
+#[cfg(feature = "embedded-hal")]
+pub struct MotorPwmChannel<'ch, 'a, S: TimerSpeed = LowSpeed> {
+    channel: &'ch Channel<'a, S>,
+    duty_resolution: Duty,
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<'ch, 'a, S: TimerSpeed> embedded_hal::pwm::ErrorType for MotorPwmChannel<'ch, 'a, S> {
+    type Error = Error;
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<'ch, 'a, S: TimerSpeed> embedded_hal::pwm::SetDutyCycle for MotorPwmChannel<'ch, 'a, S> {
+    fn max_duty_cycle(&self) -> u16 {
+        duty_resolution_max_u16(self.duty_resolution)
+    }
+
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+        self.channel.set_duty_hw(duty as u32);
+
+        Ok(())
+    }
+}
+
+/// A [`MotorFastDecay`] adapter that additionally hands out its two LEDC channels as
+/// `embedded-hal` [`SetDutyCycle`] outputs, for motion libraries (differential-drive
+/// controllers, etc.) that only know the `embedded-hal` PWM traits.
+#[cfg(feature = "embedded-hal")]
+pub struct MotorPwm<'a, S: TimerSpeed = LowSpeed> {
+    motor: MotorFastDecay<'a, S>,
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<'a, S: TimerSpeed> MotorInterface<'a, S> for MotorPwm<'a, S> {
+    fn new(a: Channel<'a, S>, b: Channel<'a, S>, duty_resolution: Duty) -> Self {
+        Self {
+            motor: MotorFastDecay::new(a, b, duty_resolution),
+        }
+    }
+
+    fn forward(&self, duty: u8) -> Result<(), Error> {
+        self.motor.forward(duty)
+    }
+
+    fn backward(&self, duty: u8) -> Result<(), Error> {
+        self.motor.backward(duty)
+    }
+
+    fn brake(&self) -> Result<(), Error> {
+        self.motor.brake()
+    }
+
+    fn forward_u16(&self, duty: u16) -> Result<(), Error> {
+        self.motor.forward_u16(duty)
+    }
+
+    fn backward_u16(&self, duty: u16) -> Result<(), Error> {
+        self.motor.backward_u16(duty)
+    }
+
+    fn fade_to(&self, duty: u8, duration: Duration) -> Result<(), Error> {
+        self.motor.fade_to(duty, duration)
+    }
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<'a, S: TimerSpeed> MotorPwm<'a, S> {
+    /// The channel driven by [`MotorInterface::forward`].
+    pub fn forward_channel(&self) -> MotorPwmChannel<'_, 'a, S> {
+        MotorPwmChannel {
+            channel: &self.motor.a,
+            duty_resolution: self.motor.duty_resolution,
+        }
+    }
+
+    /// The channel driven by [`MotorInterface::backward`].
+    pub fn backward_channel(&self) -> MotorPwmChannel<'_, 'a, S> {
+        MotorPwmChannel {
+            channel: &self.motor.b,
+            duty_resolution: self.motor.duty_resolution,
+        }
+    }
+}
+
+/// A [`MotorSlowDecay`] adapter that additionally hands out its two LEDC channels as
+/// `embedded-hal` [`SetDutyCycle`] outputs, mirroring [`MotorPwm`] for the slow-decay braking
+/// mode.
+#[cfg(feature = "embedded-hal")]
+pub struct MotorPwmSlowDecay<'a, S: TimerSpeed = LowSpeed> {
+    motor: MotorSlowDecay<'a, S>,
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<'a, S: TimerSpeed> MotorInterface<'a, S> for MotorPwmSlowDecay<'a, S> {
+    fn new(a: Channel<'a, S>, b: Channel<'a, S>, duty_resolution: Duty) -> Self {
+        Self {
+            motor: MotorSlowDecay::new(a, b, duty_resolution),
+        }
+    }
+
+    fn forward(&self, duty: u8) -> Result<(), Error> {
+        self.motor.forward(duty)
+    }
+
+    fn backward(&self, duty: u8) -> Result<(), Error> {
+        self.motor.backward(duty)
+    }
+
+    fn brake(&self) -> Result<(), Error> {
+        self.motor.brake()
+    }
+
+    fn forward_u16(&self, duty: u16) -> Result<(), Error> {
+        self.motor.forward_u16(duty)
+    }
+
+    fn backward_u16(&self, duty: u16) -> Result<(), Error> {
+        self.motor.backward_u16(duty)
+    }
+
+    fn fade_to(&self, duty: u8, duration: Duration) -> Result<(), Error> {
+        self.motor.fade_to(duty, duration)
+    }
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<'a, S: TimerSpeed> MotorPwmSlowDecay<'a, S> {
+    /// The channel driven by [`MotorInterface::forward`].
+    pub fn forward_channel(&self) -> MotorPwmChannel<'_, 'a, S> {
+        MotorPwmChannel {
+            channel: &self.motor.a,
+            duty_resolution: self.motor.duty_resolution,
+        }
+    }
+
+    /// The channel driven by [`MotorInterface::backward`].
+    pub fn backward_channel(&self) -> MotorPwmChannel<'_, 'a, S> {
+        MotorPwmChannel {
+            channel: &self.motor.b,
+            duty_resolution: self.motor.duty_resolution,
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal")]
+impl embedded_hal::pwm::Error for Error {
+    fn kind(&self) -> embedded_hal::pwm::ErrorKind {
+        embedded_hal::pwm::ErrorKind::Other
+    }
 }
 
 pub struct Stepper<'a> {
@@ -255,10 +692,223 @@ impl<'a> Stepper<'a> {
         self.output(self.sequence[self.step]);
     }
 
+    /// Rotates by `angle` degrees like [`Stepper::angle`], but aborts early with
+    /// [`Error::Stall`] if the rotor stalls, as detected via `sense`.
+    pub fn angle_sensed<ADCI, PIN, CS>(
+        &mut self,
+        angle: f32,
+        delay: &Delay,
+        sense: &mut CurrentSense<'_, ADCI, PIN, CS>,
+        r_sense_milliohm: u32,
+        stall_ma: u32,
+    ) -> Result<(), Error>
+    where
+        ADCI: RegisterAccess,
+        PIN: AdcChannel,
+        CS: AdcCalScheme<ADCI>,
+    {
+        let times = ((angle / 360.0) * self.steps_per_rev as f32) as i32;
+        let period = self.frequency.as_duration().as_micros();
+
+        if times > 0 {
+            for _ in 0..times {
+                self.step_forward_sensed(sense, r_sense_milliohm, stall_ma)?;
+                delay.delay_micros(period as u32);
+            }
+        } else {
+            for _ in 0..(times.abs()) {
+                self.step_backward_sensed(sense, r_sense_milliohm, stall_ma)?;
+                delay.delay_micros(period as u32);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Same as [`Stepper::step_forward`], but aborts with [`Error::Stall`] if the current drawn
+    /// across `r_sense_milliohm` stays at or above `stall_ma` across the step, which means the
+    /// rotor held at its stall current instead of advancing.
+    pub fn step_forward_sensed<ADCI, PIN, CS>(
+        &mut self,
+        sense: &mut CurrentSense<'_, ADCI, PIN, CS>,
+        r_sense_milliohm: u32,
+        stall_ma: u32,
+    ) -> Result<(), Error>
+    where
+        ADCI: RegisterAccess,
+        PIN: AdcChannel,
+        CS: AdcCalScheme<ADCI>,
+    {
+        let before = sense.read_milliamps(r_sense_milliohm)?;
+        self.step_forward();
+        let after = sense.read_milliamps(r_sense_milliohm)?;
+
+        if before >= stall_ma && after >= stall_ma {
+            return Err(Error::Stall);
+        }
+
+        Ok(())
+    }
+
+    /// Same as [`Stepper::step_backward`], but aborts with [`Error::Stall`] if the current drawn
+    /// across `r_sense_milliohm` stays at or above `stall_ma` across the step, which means the
+    /// rotor held at its stall current instead of advancing.
+    pub fn step_backward_sensed<ADCI, PIN, CS>(
+        &mut self,
+        sense: &mut CurrentSense<'_, ADCI, PIN, CS>,
+        r_sense_milliohm: u32,
+        stall_ma: u32,
+    ) -> Result<(), Error>
+    where
+        ADCI: RegisterAccess,
+        PIN: AdcChannel,
+        CS: AdcCalScheme<ADCI>,
+    {
+        let before = sense.read_milliamps(r_sense_milliohm)?;
+        self.step_backward();
+        let after = sense.read_milliamps(r_sense_milliohm)?;
+
+        if before >= stall_ma && after >= stall_ma {
+            return Err(Error::Stall);
+        }
+
+        Ok(())
+    }
+
     fn output(&mut self, seq: [Level; 4]) {
         self.a_plus.set_level(seq[0]);
         self.a_minus.set_level(seq[1]);
         self.b_plus.set_level(seq[2]);
         self.b_minus.set_level(seq[3]);
     }
+
+    /// Starts a non-blocking move of `steps` (negative for backward), ramping between `accel`
+    /// (steps/s²) and `max_speed` (steps/s) instead of stepping at a single fixed rate.
+    ///
+    /// Poll the returned [`StepperMotion`] with [`StepperMotion::tick`] to advance it, or use
+    /// [`StepperMotion::run_blocking`] for the old fire-and-forget behaviour.
+    pub fn move_to(&mut self, steps: i32, accel: f32, max_speed: f32) -> StepperMotion<'_, 'a> {
+        let direction = if steps >= 0 {
+            Direction::Forward
+        } else {
+            Direction::Backward
+        };
+
+        // Austin's integer-timing ramp: the first step delay is f * sqrt(2 / accel), with f
+        // the timer tick rate. We tick in microseconds, so f = 1_000_000.
+        let c0 = (1_000_000.0 * sqrtf(2.0 / accel)) as u32;
+        let min_delay_us = (1_000_000.0 / max_speed) as u32;
+        let delay_us = c0.max(min_delay_us);
+
+        StepperMotion {
+            stepper: self,
+            direction,
+            steps_remaining: steps.unsigned_abs(),
+            phase: Phase::Accelerating(0),
+            accel_steps_taken: 0,
+            delay_us,
+            min_delay_us,
+            // The first step is due after `delay_us`, not immediately: seeding `next` to
+            // `Instant::now()` alone would make tick() fire on its very first call with no
+            // ramp-up delay at all.
+            next: Instant::now() + Duration::from_micros(delay_us as u64),
+        }
+    }
+}
+
+/// Which leg of the trapezoidal ramp [`StepperMotion`] is currently on.
+enum Phase {
+    /// Holds the number of steps taken so far in the ramp-up.
+    Accelerating(u32),
+    /// Running at `min_delay_us`, the fastest rate `max_speed` allows.
+    Cruising,
+    /// Holds the number of steps left before the move completes; mirrors ramp-up in reverse.
+    Decelerating(u32),
+}
+
+/// A non-blocking stepper move created by [`Stepper::move_to`], driving a trapezoidal
+/// acceleration profile: ramp up, optionally cruise at `max_speed`, then ramp down so the
+/// move ends stopped.
+pub struct StepperMotion<'s, 'a> {
+    stepper: &'s mut Stepper<'a>,
+    direction: Direction,
+    steps_remaining: u32,
+    phase: Phase,
+    accel_steps_taken: u32,
+    delay_us: u32,
+    min_delay_us: u32,
+    next: Instant,
+}
+
+impl<'s, 'a> StepperMotion<'s, 'a> {
+    /// Emits the next step if `now` has reached the scheduled instant, and advances the ramp.
+    ///
+    /// Returns whether a step was emitted. Once the move is complete, every call returns
+    /// `false` without touching the stepper.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if self.steps_remaining == 0 || now < self.next {
+            return false;
+        }
+
+        match self.direction {
+            Direction::Forward => self.stepper.step_forward(),
+            Direction::Backward => self.stepper.step_backward(),
+        }
+
+        self.steps_remaining -= 1;
+
+        match self.phase {
+            Phase::Accelerating(n) => {
+                let n = n + 1;
+                // Widen to u64: `delay_us` can be close to u32::MAX for very small `accel`,
+                // and `2 * delay_us` would overflow u32 before the division brings it back down.
+                self.delay_us -= ((2 * self.delay_us as u64) / (4 * n as u64 + 1)) as u32;
+
+                if self.delay_us <= self.min_delay_us {
+                    self.delay_us = self.min_delay_us;
+                    self.accel_steps_taken = n;
+                    self.phase = if self.steps_remaining <= n {
+                        Phase::Decelerating(n)
+                    } else {
+                        Phase::Cruising
+                    };
+                } else if self.steps_remaining <= n {
+                    // Hit the symmetric midpoint before reaching max_speed: start decelerating.
+                    self.accel_steps_taken = n;
+                    self.phase = Phase::Decelerating(n);
+                } else {
+                    self.phase = Phase::Accelerating(n);
+                }
+            }
+            Phase::Cruising => {
+                if self.steps_remaining <= self.accel_steps_taken {
+                    self.phase = Phase::Decelerating(self.accel_steps_taken);
+                }
+            }
+            Phase::Decelerating(n) => {
+                // Same recurrence as ramp-up with `4n + 1` replaced by `4n - 1`, so the delay
+                // grows back towards c0 instead of shrinking further. The phase is entered with
+                // n >= 1 and its count is floored at 1 as it winds down, so `4 * n - 1` never
+                // sees n == 0, where it would underflow.
+                let n = n.max(1);
+                self.delay_us += ((2 * self.delay_us as u64) / (4 * n as u64 - 1)) as u32;
+                self.phase = Phase::Decelerating(n.saturating_sub(1).max(1));
+            }
+        }
+
+        self.next = now + Duration::from_micros(self.delay_us as u64);
+
+        true
+    }
+
+    /// Runs the move to completion, busy-polling [`StepperMotion::tick`] with `delay` between
+    /// checks. Blocks the caller like [`Stepper::angle`], but follows the acceleration ramp
+    /// instead of stepping at a single fixed rate.
+    pub fn run_blocking(&mut self, delay: &Delay) {
+        while self.steps_remaining > 0 {
+            if !self.tick(Instant::now()) {
+                delay.delay_micros(1);
+            }
+        }
+    }
 }
@@ -13,6 +13,10 @@
 //! * It is more power efficient.
 //! * It can still work under sleep modes.
 //!
+//! [`MotorTimer`] is generic over the LEDC speed domain, so the high-speed domain remains
+//! available for when a higher duty resolution matters more than the above (see
+//! [`MotorTimer::new_high_speed`]); everything in this crate defaults to the low-speed domain.
+//!
 //! ### Drive forward with 100% duty cycle
 //!
 //! The followig example shows how to use the crate to drive a brushed motor
@@ -54,6 +58,16 @@
 //! motor.brake()?;
 //! ```
 //!
+//! ### Fade to a duty cycle over time
+//!
+//! Uses the LEDC hardware fade instead of jumping straight to the target duty, which is
+//! gentler on brushed motors:
+//!
+//! ```rust
+//! motor.forward(0)?;
+//! motor.fade_to(100, Duration::from_secs(2))?;
+//! ```
+//!
 //! ### Setup a slow decay motor
 //!
 //! ```rust
@@ -64,6 +78,21 @@
 //! )?;
 //! ```
 //!
+//! ### Drive with a high-resolution duty
+//!
+//! `forward`/`backward` take a 0-100 percentage; `forward_u16`/`backward_u16` take a
+//! 0-65535 duty rescaled to the timer's configured resolution, for finer control:
+//!
+//! ```rust
+//! motor.forward_u16(40000)?;
+//! ```
+//!
+//! ### Change the PWM frequency at runtime
+//!
+//! ```rust
+//! motor_conf.set_frequency(Rate::from_khz(2))?;
+//! ```
+//!
 //! ### Setup two motors
 //!
 //! ```rust
@@ -81,12 +110,94 @@
 //!     MotorLink::new(channel::Number::Channel3, peripherals.GPIO4),
 //! )?;
 //! ```
+//!
+//! ### Drive forward with over-current protection
+//!
+//! [`CurrentSense`] wraps an ADC channel tied to the DRV8833 sense-resistor node; pass it to
+//! `forward_guarded` to have the motor brake itself once the current it draws crosses
+//! `threshold_ma`:
+//!
+//! ```rust
+//! let mut current_sense = CurrentSense::new(adc1, sense_pin);
+//! let delay = Delay::new();
+//!
+//! motor.forward_guarded(
+//!     100,
+//!     &mut current_sense,
+//!     100,
+//!     1500,
+//!     &delay,
+//!     Duration::from_millis(1),
+//!     Duration::from_millis(50),
+//! )?;
+//! ```
+//!
+//! ### Drive with a generic `embedded-hal` motion library
+//!
+//! Behind the `embedded-hal` feature, [`MotorPwm`] (and [`MotorPwmSlowDecay`], for the
+//! slow-decay braking mode) exposes its two channels as [`embedded_hal::pwm::SetDutyCycle`]
+//! outputs, with `max_duty_cycle` reporting the timer's actual configured resolution:
+//!
+//! ```rust
+//! # #[cfg(feature = "embedded-hal")]
+//! # {
+//! let motor: MotorPwm = Motor::new(&ledc, &motor_timer_conf, motor_link_a, motor_link_b)?;
+//!
+//! motor.forward_channel().set_duty_cycle_percent(50)?;
+//! # }
+//! ```
+//!
+//! ### Non-blocking stepper move with acceleration
+//!
+//! [`Stepper::move_to`] ramps between an acceleration and a max speed instead of stepping at a
+//! single fixed rate, and can be polled instead of blocking the caller:
+//!
+//! ```rust
+//! let mut motion = stepper.move_to(200, 500.0, 1000.0);
+//!
+//! loop {
+//!     if !motion.tick(Instant::now()) {
+//!         // free to do other work here between steps
+//!     }
+//! }
+//! ```
+//!
+//! ### Setup a motor on the high-speed LEDC domain
+//!
+//! Only available on the original ESP32 (`#[cfg(esp32)]`) — later ESP32 variants, including the
+//! `esp32c6` this crate otherwise targets, dropped the LEDC high-speed domain entirely:
+//!
+//! ```rust
+//! let motor_timer_conf = MotorTimer::new_high_speed(
+//!     &ledc,
+//!     timer::Number::Timer0,
+//!     timer::config::Duty::Duty11Bit,
+//!     Rate::from_khz(20),
+//! )?;
+//!
+//! let motor: MotorFastDecay<HighSpeed> = Motor::new(
+//!     &ledc,
+//!     &motor_timer_conf,
+//!     MotorLink::new(channel::Number::Channel0, peripherals.GPIO1),
+//!     MotorLink::new(channel::Number::Channel1, peripherals.GPIO2),
+//! )?;
+//! ```
 
+pub mod current_sense;
 pub mod drv8833;
 
+pub use current_sense::CurrentSense;
 pub use drv8833::Motor;
 pub use drv8833::MotorFastDecay;
 pub use drv8833::MotorInterface;
 pub use drv8833::MotorSlowDecay;
 pub use drv8833::MotorTimer;
 pub use drv8833::Stepper;
+pub use drv8833::StepperMotion;
+
+#[cfg(feature = "embedded-hal")]
+pub use drv8833::MotorPwm;
+#[cfg(feature = "embedded-hal")]
+pub use drv8833::MotorPwmChannel;
+#[cfg(feature = "embedded-hal")]
+pub use drv8833::MotorPwmSlowDecay;